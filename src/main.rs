@@ -2,18 +2,194 @@ use chrono::Local;
 use dialoguer::{Input, Select};
 use indicatif::{ProgressBar, ProgressStyle};
 use serde::{Deserialize, Serialize};
-use std::{env, fs, io::Write, process::Command, sync::mpsc, thread, time::Duration};
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
 
 #[derive(Serialize, Deserialize)]
 struct Config {
     directories: Vec<String>,
     command_sets: Vec<CommandSet>,
+    #[serde(default)]
+    plugins: Vec<String>,
+    #[serde(default)]
+    env: BTreeMap<String, String>,
+    #[serde(default)]
+    aliases: BTreeMap<String, String>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 struct CommandSet {
     name: String,
-    commands: Vec<String>,
+    commands: Vec<CommandSpec>,
+    /// Name of the plugin that backs this set, if it was provided by one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    plugin: Option<String>,
+}
+
+/// How a single command is run. A plain JSON string is a normal blocking
+/// command; an object unlocks long-running/watch behaviour.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+enum CommandSpec {
+    Simple(String),
+    Detailed {
+        cmd: String,
+        #[serde(default)]
+        mode: CommandMode,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_secs: Option<u64>,
+        #[serde(default)]
+        kill_after: bool,
+    },
+}
+
+/// Execution mode for a [`CommandSpec`].
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum CommandMode {
+    /// Run to completion, failing the set on a non-zero exit (the default).
+    #[default]
+    Normal,
+    /// Spawn, show a spinner until the timeout elapses or the process exits,
+    /// then optionally kill it.
+    Watch,
+}
+
+impl CommandSpec {
+    /// The command line to execute.
+    fn cmd(&self) -> &str {
+        match self {
+            CommandSpec::Simple(cmd) => cmd,
+            CommandSpec::Detailed { cmd, .. } => cmd,
+        }
+    }
+
+    fn mode(&self) -> &CommandMode {
+        match self {
+            CommandSpec::Simple(_) => &CommandMode::Normal,
+            CommandSpec::Detailed { mode, .. } => mode,
+        }
+    }
+
+    fn timeout_secs(&self) -> Option<u64> {
+        match self {
+            CommandSpec::Detailed { timeout_secs, .. } => *timeout_secs,
+            CommandSpec::Simple(_) => None,
+        }
+    }
+
+    fn kill_after(&self) -> bool {
+        matches!(self, CommandSpec::Detailed { kill_after: true, .. })
+    }
+}
+
+/// What a plugin reports in response to the `config` request: its name plus the
+/// command sets and hooks it contributes to `cmdy`.
+#[derive(Serialize, Deserialize)]
+struct Signature {
+    name: String,
+    #[serde(default)]
+    command_sets: Vec<CommandSet>,
+    #[serde(default)]
+    hooks: Vec<String>,
+}
+
+/// A running plugin child process we talk to over line-delimited JSON-RPC on
+/// its stdin/stdout. The child is kept alive for as long as the handle lives
+/// and is killed on drop.
+struct Plugin {
+    name: String,
+    child: Child,
+    stdin: ChildStdin,
+    reader: BufReader<ChildStdout>,
+}
+
+impl Drop for Plugin {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+    }
+}
+
+impl Plugin {
+    /// Ask the plugin to run a command set in `directory`, driving `pb` from the
+    /// `{"type":"ok"}` / `{"type":"error","msg":...}` lines it streams back.
+    /// `commands` must already have `${VAR}`/alias substitution applied, the
+    /// same as the commands handed to `sh -c` for a non-plugin set. Returns
+    /// `true` if every command succeeded.
+    fn run_set(&mut self, commands: &[String], directory: &str, pb: &ProgressBar) -> bool {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "run",
+            "params": { "commands": commands, "directory": directory },
+        });
+        if writeln!(self.stdin, "{}", request).and_then(|_| self.stdin.flush()).is_err() {
+            eprintln!("❌ Failed to send run request to plugin: {}", self.name);
+            return false;
+        }
+
+        let mut acked = 0usize;
+        loop {
+            let mut line = String::new();
+            match self.reader.read_line(&mut line) {
+                // The stream ended before every command was acknowledged: treat a
+                // crashed or prematurely-closed plugin as a failed run.
+                Ok(0) => break,
+                Ok(_) => {}
+                Err(_) => return false,
+            }
+            let response: serde_json::Value = match serde_json::from_str(line.trim()) {
+                Ok(value) => value,
+                Err(_) => continue,
+            };
+            match response.get("type").and_then(|t| t.as_str()) {
+                Some("ok") => {
+                    pb.inc(1);
+                    acked += 1;
+                }
+                Some("error") => {
+                    let msg = response.get("msg").and_then(|m| m.as_str()).unwrap_or("unknown");
+                    eprintln!("❌ Plugin command failed: {}", msg);
+                    return false;
+                }
+                _ => {}
+            }
+            // A plugin may flag the final response, otherwise we stop once every
+            // command in the set has been acknowledged.
+            if response.get("done").and_then(|d| d.as_bool()).unwrap_or(false) {
+                break;
+            }
+            if acked >= commands.len() {
+                break;
+            }
+        }
+        acked >= commands.len()
+    }
+}
+
+/// A recorded run of a command set: a header plus the timestamped output events
+/// captured while it executed.
+#[derive(Serialize, Deserialize)]
+struct Cast {
+    set: String,
+    directory: String,
+    events: Vec<CastEvent>,
+}
+
+/// A single recorded event: output text (or an `exit` code) on a named stream,
+/// timed relative to the start of the recording.
+#[derive(Serialize, Deserialize)]
+struct CastEvent {
+    offset_ms: u128,
+    stream: String,
+    text: String,
 }
 
 const CONFIG_FILE: &str = "config.json";
@@ -28,13 +204,216 @@ fn load_config() -> Config {
 
 /// Save the current configuration to the JSON file
 fn save_config(config: &Config) {
-    let config_data = serde_json::to_string_pretty(config).expect("Failed to serialize config");
+    // Plugin-advertised sets live only in memory; persisting them would both
+    // duplicate them on the next load and orphan their commands to `sh -c` if
+    // the plugin goes away.
+    let persistent = Config {
+        directories: config.directories.clone(),
+        command_sets: config
+            .command_sets
+            .iter()
+            .filter(|set| set.plugin.is_none())
+            .cloned()
+            .collect(),
+        plugins: config.plugins.clone(),
+        env: config.env.clone(),
+        aliases: config.aliases.clone(),
+    };
+    let config_data =
+        serde_json::to_string_pretty(&persistent).expect("Failed to serialize config");
     fs::write(CONFIG_FILE, config_data).expect("Failed to save config file");
 }
 
+/// Spawn a plugin executable and perform the JSON-RPC `config` handshake,
+/// returning the live child handle and the signature it advertised.
+fn load_plugin(path: &str) -> std::io::Result<(Plugin, Signature)> {
+    let mut child = Command::new(path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+    let mut stdin = child.stdin.take().expect("plugin stdin was piped");
+    let stdout = child.stdout.take().expect("plugin stdout was piped");
+
+    stdin.write_all(b"{\"jsonrpc\":\"2.0\",\"method\":\"config\",\"params\":[]}\n")?;
+    stdin.flush()?;
+
+    let mut reader = BufReader::new(stdout);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let signature: Signature =
+        serde_json::from_str(line.trim()).expect("Failed to parse plugin signature");
+
+    let plugin = Plugin {
+        name: signature.name.clone(),
+        child,
+        stdin,
+        reader,
+    };
+    Ok((plugin, signature))
+}
+
+/// Collect candidate plugin paths: those listed in `config.plugins` plus any
+/// `cmdy_plugin_*` executable found on `PATH`.
+fn discover_plugins(config: &Config) -> Vec<String> {
+    let mut paths = config.plugins.clone();
+    if let Ok(path_var) = env::var("PATH") {
+        for dir in env::split_paths(&path_var) {
+            if let Ok(entries) = fs::read_dir(&dir) {
+                for entry in entries.flatten() {
+                    if let Some(name) = entry.file_name().to_str() {
+                        if name.starts_with("cmdy_plugin_") {
+                            paths.push(entry.path().to_string_lossy().to_string());
+                        }
+                    }
+                }
+            }
+        }
+    }
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+/// Load every discovered plugin and register the command sets it provides into
+/// the in-memory config, returning the live handles to run them later.
+fn load_plugins(config: &mut Config) -> Vec<Plugin> {
+    let mut plugins = Vec::new();
+    for path in discover_plugins(config) {
+        match load_plugin(&path) {
+            Ok((plugin, signature)) => {
+                for mut set in signature.command_sets {
+                    set.plugin = Some(plugin.name.clone());
+                    config.command_sets.push(set);
+                }
+                plugins.push(plugin);
+            }
+            Err(e) => eprintln!("⚠️  Failed to load plugin {}: {}", path, e),
+        }
+    }
+    plugins
+}
+
+/// Build the substitution map for a run: the process environment overlaid with
+/// `config.env`, plus the special `DIR` (chosen directory) and `?` (previous
+/// command's exit status, starting at `0`) keys.
+fn build_env(config: &Config, directory: &str) -> BTreeMap<String, String> {
+    let mut map: BTreeMap<String, String> = env::vars().collect();
+    map.extend(config.env.clone());
+    map.insert("DIR".to_string(), directory.to_string());
+    map.insert("?".to_string(), "0".to_string());
+    map
+}
+
+/// Expand `${VAR}` references in `input` against `env`; unknown variables expand
+/// to the empty string.
+fn expand_vars(input: &str, env: &BTreeMap<String, String>) -> String {
+    let mut out = String::new();
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '$' && chars.peek() == Some(&'{') {
+            chars.next();
+            let mut name = String::new();
+            for c in chars.by_ref() {
+                if c == '}' {
+                    break;
+                }
+                name.push(c);
+            }
+            if let Some(value) = env.get(&name) {
+                out.push_str(value);
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Resolve a leading alias token in `cmd` to its expansion, leaving the rest of
+/// the command line untouched.
+fn resolve_alias(cmd: &str, aliases: &BTreeMap<String, String>) -> String {
+    let mut parts = cmd.splitn(2, char::is_whitespace);
+    let first = parts.next().unwrap_or("");
+    match aliases.get(first) {
+        Some(expansion) => match parts.next() {
+            Some(rest) => format!("{} {}", expansion, rest),
+            None => expansion.clone(),
+        },
+        None => cmd.to_string(),
+    }
+}
+
+/// Offer filesystem completions for the path being typed in `line`: read the
+/// parent directory and return the sorted full-path candidates whose last token
+/// matches the current prefix (directories gain a trailing `/`).
+fn completer(line: &str) -> Vec<String> {
+    let (dir, prefix) = if line.ends_with('/') {
+        (PathBuf::from(line), String::new())
+    } else {
+        let path = Path::new(line);
+        let parent = path.parent().filter(|p| !p.as_os_str().is_empty());
+        let prefix = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("")
+            .to_string();
+        (parent.map(Path::to_path_buf).unwrap_or_else(|| PathBuf::from(".")), prefix)
+    };
+
+    let mut candidates = Vec::new();
+    if let Ok(entries) = fs::read_dir(&dir) {
+        for entry in entries.flatten() {
+            if let Some(name) = entry.file_name().to_str() {
+                if name.starts_with(&prefix) {
+                    let mut candidate = dir.join(name).to_string_lossy().to_string();
+                    if entry.path().is_dir() {
+                        candidate.push('/');
+                    }
+                    candidates.push(candidate);
+                }
+            }
+        }
+    }
+    candidates.sort();
+    candidates
+}
+
+/// Prompt for a line of input, offering `candidates` as a pick list so the user
+/// can select a completion instead of typing it out; falls back to a plain text
+/// prompt when there's nothing to complete against or the user wants to type
+/// something new. This mirrors the `Select` + "enter manually" escape hatch
+/// `run` already uses for directories and command sets, rather than relying on
+/// `dialoguer`'s optional `completion` cargo feature.
+fn prompt_with_completion(prompt: &str, candidates: Vec<String>) -> String {
+    if candidates.is_empty() {
+        return Input::new().with_prompt(prompt).interact_text().unwrap();
+    }
+
+    let mut items = candidates;
+    items.push("Type manually...".to_string());
+    let choice = Select::new()
+        .with_prompt(prompt)
+        .items(&items)
+        .default(0)
+        .interact()
+        .unwrap();
+
+    if choice == items.len() - 1 {
+        Input::new().with_prompt(prompt).interact_text().unwrap()
+    } else {
+        items[choice].clone()
+    }
+}
+
 /// Execute the selected command set in the specified directory
 
-fn execute_commands(commands: &[String], directory: &String, set: &CommandSet) {
+fn execute_commands(
+    commands: &[CommandSpec],
+    directory: &String,
+    set: &CommandSet,
+    config: &Config,
+    plugin: Option<&mut Plugin>,
+) {
     println!("\n🚀 Executing command set: {}", set.name);
     let pb = ProgressBar::new(commands.len() as u64);
     pb.set_style(ProgressStyle::default_bar()
@@ -42,32 +421,36 @@ fn execute_commands(commands: &[String], directory: &String, set: &CommandSet) {
         .unwrap()
         .progress_chars("#>-"));
 
-    for (_, cmd) in commands.iter().enumerate() {
+    let mut env = build_env(config, directory);
+
+    if let Some(plugin) = plugin {
+        // Plugin-backed sets go over the wire, but they get the same
+        // `${VAR}`/alias substitution as a local `sh -c` set (chunk0-2) so a
+        // plugin set author can't tell the difference.
+        let expanded: Vec<String> = commands
+            .iter()
+            .map(|spec| expand_vars(&resolve_alias(spec.cmd(), &config.aliases), &env))
+            .collect();
+        if plugin.run_set(&expanded, directory, &pb) {
+            pb.finish_with_message("✅ All commands executed successfully!");
+            log_execution(set);
+        }
+        return;
+    }
+
+    for spec in commands.iter() {
+        let cmd = expand_vars(&resolve_alias(spec.cmd(), &config.aliases), &env);
+        let cmd = cmd.as_str();
         println!("🔹 Running: {}", cmd);
         let mut command = Command::new("sh");
         command.arg("-c").arg(cmd).current_dir(directory);
 
-        if cmd == "npm run dev" {
-            let mut child = command.spawn().expect("Failed to start process");
-            let (tx, rx) = mpsc::channel();
-            let spinner_thread = thread::spawn(move || {
-                let spinner = ["|", "/", "-", "\\"];
-                let mut i = 0;
-                while rx.try_recv().is_err() {
-                    print!("\rSpinning Deployment Server... {}", spinner[i % 4]);
-                    std::io::stdout().flush().unwrap();
-                    i += 1;
-                    thread::sleep(Duration::from_millis(200));
-                }
-                println!("\rComplete!            ");
-            });
-            thread::sleep(Duration::from_secs(10));
-            child.kill().expect("Failed to stop npm run dev");
-            tx.send(()).unwrap();
-            spinner_thread.join().unwrap();
-            println!("npm run dev stopped after pulling the vault.");
+        if spec.mode() == &CommandMode::Watch {
+            let code = run_watch(command, cmd, spec.timeout_secs(), spec.kill_after());
+            env.insert("?".to_string(), code.to_string());
         } else {
             let status = command.status().expect("Failed to execute command");
+            env.insert("?".to_string(), status.code().unwrap_or(-1).to_string());
             if !status.success() {
                 eprintln!("❌ Command failed: {}", cmd);
                 return;
@@ -79,6 +462,91 @@ fn execute_commands(commands: &[String], directory: &String, set: &CommandSet) {
     log_execution(set);
 }
 
+/// Run a long-running/watch command: spawn it, spin a spinner until the process
+/// exits on its own or `timeout_secs` elapses, then optionally kill it. Returns
+/// the exit status to surface as `?`: the process's own code when it exited,
+/// `-1` when it was killed, and `0` when it was left running past the timeout.
+fn run_watch(mut command: Command, cmd: &str, timeout_secs: Option<u64>, kill_after: bool) -> i32 {
+    let mut child = command.spawn().expect("Failed to start process");
+    let (tx, rx) = mpsc::channel();
+    let spinner_cmd = cmd.to_string();
+    let spinner_thread = thread::spawn(move || {
+        let spinner = ["|", "/", "-", "\\"];
+        let mut i = 0;
+        while rx.try_recv().is_err() {
+            print!("\rWatching {} {}", spinner_cmd, spinner[i % 4]);
+            std::io::stdout().flush().unwrap();
+            i += 1;
+            thread::sleep(Duration::from_millis(200));
+        }
+        println!("\rComplete!            ");
+    });
+
+    let deadline = timeout_secs.map(|secs| Instant::now() + Duration::from_secs(secs));
+    let mut exit_code = None;
+    loop {
+        if let Some(status) = child.try_wait().expect("Failed to poll process") {
+            exit_code = Some(status.code().unwrap_or(-1));
+            break;
+        }
+        if deadline.is_some_and(|d| Instant::now() >= d) {
+            break;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    let killed = if exit_code.is_none() && kill_after {
+        child.kill().expect("Failed to stop watch command");
+        let _ = child.wait();
+        true
+    } else {
+        false
+    };
+
+    tx.send(()).unwrap();
+    spinner_thread.join().unwrap();
+    match exit_code {
+        Some(code) => {
+            println!("{} exited on its own (status {}).", cmd, code);
+            code
+        }
+        None if killed => {
+            println!("{} killed after timeout.", cmd);
+            -1
+        }
+        None => {
+            println!("{} left running after timeout.", cmd);
+            0
+        }
+    }
+}
+
+/// Bound how long `record` waits on a watch-mode child: poll until it exits on
+/// its own or `timeout_secs` elapses, then optionally kill it. Returns the exit
+/// status to record, using the same convention as [`run_watch`]: the process's
+/// own code when it exited, `-1` when it was killed, and `0` when it was left
+/// running past the timeout.
+fn record_watch(child: &mut Child, timeout_secs: Option<u64>, kill_after: bool) -> i32 {
+    let deadline = timeout_secs.map(|secs| Instant::now() + Duration::from_secs(secs));
+    loop {
+        if let Some(status) = child.try_wait().expect("Failed to poll process") {
+            return status.code().unwrap_or(-1);
+        }
+        if deadline.is_some_and(|d| Instant::now() >= d) {
+            break;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    if kill_after {
+        child.kill().expect("Failed to stop watch command");
+        let _ = child.wait();
+        -1
+    } else {
+        0
+    }
+}
+
 /// Log executed command sets
 fn log_execution(command_set: &CommandSet) {
     let log_entry = format!(
@@ -122,9 +590,126 @@ fn delete_command(command_name: &str) {
     println!("🗑️ Deleted command set: {}", command_name);
 }
 
+/// Record a run of a command set into a JSON "cast" file, capturing each
+/// command's stdout/stderr line-by-line with timings and exit codes.
+fn record(set_name: &str) {
+    let config = load_config();
+    let set = match config.command_sets.iter().find(|s| s.name == set_name) {
+        Some(set) => set,
+        None => {
+            eprintln!("❌ No command set named: {}", set_name);
+            return;
+        }
+    };
+
+    let directory = env::current_dir().unwrap().to_string_lossy().to_string();
+    let mut env = build_env(&config, &directory);
+    let start = Instant::now();
+    let mut events: Vec<CastEvent> = Vec::new();
+
+    for spec in &set.commands {
+        let cmd = expand_vars(&resolve_alias(spec.cmd(), &config.aliases), &env);
+        println!("🔹 Recording: {}", cmd);
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(&cmd)
+            .current_dir(&directory)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("Failed to start process");
+
+        let stdout = child.stdout.take().expect("stdout was piped");
+        let stderr = child.stderr.take().expect("stderr was piped");
+        let (tx, rx) = mpsc::channel();
+        let tx_err = tx.clone();
+        let stdout_thread = thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                let _ = tx.send((start.elapsed().as_millis(), "stdout", line));
+            }
+        });
+        let stderr_thread = thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                let _ = tx_err.send((start.elapsed().as_millis(), "stderr", line));
+            }
+        });
+
+        // Bound the wait the same way `run_watch` does for a watch-mode command,
+        // instead of always blocking on `child.wait()` — otherwise recording a
+        // long-running/dev-server entry would hang the recording forever. The
+        // reader threads only join once the child has exited or been killed, at
+        // which point its own ends of the pipes are closed. A watch command left
+        // running past its timeout is not waited on: only the output it produced
+        // so far is recorded.
+        let exit_code = if spec.mode() == &CommandMode::Watch {
+            record_watch(&mut child, spec.timeout_secs(), spec.kill_after())
+        } else {
+            child.wait().expect("Failed to wait for process").code().unwrap_or(-1)
+        };
+        if matches!(child.try_wait(), Ok(Some(_))) {
+            stdout_thread.join().unwrap();
+            stderr_thread.join().unwrap();
+        }
+
+        for (offset_ms, stream, text) in rx.try_iter() {
+            events.push(CastEvent {
+                offset_ms,
+                stream: stream.to_string(),
+                text,
+            });
+        }
+        events.push(CastEvent {
+            offset_ms: start.elapsed().as_millis(),
+            stream: "exit".to_string(),
+            text: exit_code.to_string(),
+        });
+        // Keep `?` in sync with the real outcome, the same way `execute_commands`
+        // does, so `${?}` expands the same during a recording as during a live run.
+        env.insert("?".to_string(), exit_code.to_string());
+    }
+
+    events.sort_by_key(|event| event.offset_ms);
+    let cast = Cast {
+        set: set.name.clone(),
+        directory,
+        events,
+    };
+    let file = format!("{}.cast.json", set_name);
+    fs::write(&file, serde_json::to_string_pretty(&cast).unwrap())
+        .expect("Failed to write cast file");
+    println!("📼 Recorded {} events to {}", cast.events.len(), file);
+    log_execution(set);
+}
+
+/// Replay a recorded cast file, re-rendering the captured output with the
+/// original inter-event delays.
+fn play(file: &str) {
+    let data = fs::read_to_string(file).expect("Failed to read cast file");
+    let cast: Cast = serde_json::from_str(&data).expect("Failed to parse cast file");
+    println!(
+        "▶️  Replaying set '{}' (recorded in {})",
+        cast.set, cast.directory
+    );
+
+    let mut last = 0u128;
+    for event in &cast.events {
+        let delay = event.offset_ms.saturating_sub(last);
+        if delay > 0 {
+            thread::sleep(Duration::from_millis(delay as u64));
+        }
+        last = event.offset_ms;
+        match event.stream.as_str() {
+            "stderr" => eprintln!("{}", event.text),
+            "exit" => println!("— exit {}", event.text),
+            _ => println!("{}", event.text),
+        }
+    }
+}
+
 /// Run the CLI workflow
 fn run() {
     let mut config = load_config();
+    let mut plugins = load_plugins(&mut config);
     let selection = Select::new()
         .with_prompt("Select a directory")
         .item("Current Directory")
@@ -136,10 +721,7 @@ fn run() {
     let directory = if selection == 0 {
         env::current_dir().unwrap().to_string_lossy().to_string()
     } else if selection == config.directories.len() + 1 {
-        let new_dir: String = Input::new()
-            .with_prompt("Enter directory path")
-            .interact_text()
-            .unwrap();
+        let new_dir = prompt_with_completion("Enter directory path", completer(""));
         config.directories.push(new_dir.clone());
         save_config(&config);
         new_dir
@@ -160,10 +742,7 @@ fn run() {
         .unwrap();
 
     let command_set_name = if command_set_name == command_set_names.len() {
-        Input::new()
-            .with_prompt("Enter new command set name")
-            .interact_text()
-            .unwrap()
+        prompt_with_completion("Enter new command set name", command_set_names.clone())
     } else {
         command_set_names[command_set_name].clone()
     };
@@ -179,11 +758,12 @@ fn run() {
             .unwrap();
         let commands = commands_input
             .split(',')
-            .map(|s| s.trim().to_string())
+            .map(|s| CommandSpec::Simple(s.trim().to_string()))
             .collect();
         config.command_sets.push(CommandSet {
             name: command_set_name.clone(),
             commands,
+            plugin: None,
         });
         save_config(&config);
     }
@@ -193,30 +773,252 @@ fn run() {
         .iter()
         .find(|set| set.name == command_set_name)
     {
-        execute_commands(&set.commands, &directory, set);
+        let plugin = set
+            .plugin
+            .as_ref()
+            .and_then(|name| plugins.iter_mut().find(|p| &p.name == name));
+        execute_commands(&set.commands, &directory, set, &config, plugin);
     }
 }
 
-/// Display help menu
+/// A CLI subcommand: its name, the function that runs it (given the full
+/// argument vector), a one-line help string, and the log verbosity to apply
+/// while it runs — `"debug"` and `"warn"` entries get a diagnostic line
+/// printed before dispatch (see `main`), `"info"` entries are silent.
+struct Subcommand {
+    name: &'static str,
+    run: fn(&[String]),
+    help: &'static str,
+    log_level: &'static str,
+}
+
+/// The registry of every subcommand. Adding a command here keeps both dispatch
+/// and `help` in sync.
+static COMMANDS: &[Subcommand] = &[
+    Subcommand {
+        name: "run",
+        run: |_| run(),
+        help: "Run a command set",
+        log_level: "info",
+    },
+    Subcommand {
+        name: "list",
+        run: |_| list_commands(),
+        help: "List all command sets",
+        log_level: "info",
+    },
+    Subcommand {
+        name: "logs",
+        run: |_| view_logs(),
+        help: "View execution logs",
+        log_level: "debug",
+    },
+    Subcommand {
+        name: "delete",
+        run: cmd_delete,
+        help: "Delete a command set: delete <name>",
+        log_level: "warn",
+    },
+    Subcommand {
+        name: "record",
+        run: cmd_record,
+        help: "Record a run of a command set to a cast file: record <set>",
+        log_level: "info",
+    },
+    Subcommand {
+        name: "play",
+        run: cmd_play,
+        help: "Replay a recorded cast file: play <file>",
+        log_level: "info",
+    },
+    Subcommand {
+        name: "help",
+        run: |_| help(),
+        help: "Show this help message",
+        log_level: "info",
+    },
+];
+
+/// Adapter: `delete <name>`.
+fn cmd_delete(args: &[String]) {
+    match args.get(2) {
+        Some(name) => delete_command(name),
+        None => eprintln!("Usage: cmdy delete <name>"),
+    }
+}
+
+/// Adapter: `record <set>`.
+fn cmd_record(args: &[String]) {
+    match args.get(2) {
+        Some(set) => record(set),
+        None => eprintln!("Usage: cmdy record <set>"),
+    }
+}
+
+/// Adapter: `play <file>`.
+fn cmd_play(args: &[String]) {
+    match args.get(2) {
+        Some(file) => play(file),
+        None => eprintln!("Usage: cmdy play <file>"),
+    }
+}
+
+/// Display help menu, generated from the command registry.
 fn help() {
     println!("\n📌 cmdy CLI - Enhanced Command Manager");
     println!("------------------------------------");
-    println!("cmdy run         - Run a command set");
-    println!("cmdy list        - List all command sets");
-    println!("cmdy logs        - View execution logs");
-    println!("cmdy delete <name> - Delete a command set");
-    println!("cmdy help        - Show this help message\n");
+    for cmd in COMMANDS {
+        println!("cmdy {:<8} - {}", cmd.name, cmd.help);
+    }
+    println!();
 }
 
 /// Entry point of the CLI application
 fn main() {
     let args: Vec<String> = env::args().collect();
-    match args.get(1).map(String::as_str) {
-        Some("run") => run(),
-        Some("list") => list_commands(),
-        Some("logs") => view_logs(),
-        Some("delete") if args.len() > 2 => delete_command(&args[2]),
-        Some("help") | Some("--help") => help(),
-        _ => println!("Usage: cmdy <command>. Use 'cmdy help' for details."),
+    let name = match args.get(1).map(String::as_str) {
+        Some("--help") => "help",
+        Some(name) => name,
+        None => {
+            println!("Usage: cmdy <command>. Use 'cmdy help' for details.");
+            return;
+        }
+    };
+
+    match COMMANDS.iter().find(|cmd| cmd.name == name) {
+        Some(cmd) => {
+            match cmd.log_level {
+                "debug" => eprintln!("[debug] running '{}'", cmd.name),
+                "warn" => eprintln!("[warn] running '{}'", cmd.name),
+                _ => {}
+            }
+            (cmd.run)(&args);
+        }
+        None => println!("Usage: cmdy <command>. Use 'cmdy help' for details."),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn expand_vars_substitutes_known_names() {
+        let env = env(&[("DIR", "/tmp/proj"), ("?", "0")]);
+        assert_eq!(expand_vars("cd ${DIR} && echo ${?}", &env), "cd /tmp/proj && echo 0");
+    }
+
+    #[test]
+    fn expand_vars_unknown_name_expands_to_empty() {
+        let env = env(&[]);
+        assert_eq!(expand_vars("echo ${MISSING}", &env), "echo ");
+    }
+
+    #[test]
+    fn expand_vars_unterminated_brace_consumes_to_end_of_string() {
+        // No closing `}`: the scan runs off the end of the input, so everything
+        // after `${` is swallowed into the variable name with no diagnostic —
+        // an unrelated variable earlier in the map is untouched.
+        let env = env(&[("DIR", "/tmp/proj")]);
+        assert_eq!(expand_vars("cd ${DIR} && echo ${NAME", &env), "cd /tmp/proj && echo ");
+    }
+
+    #[test]
+    fn resolve_alias_expands_leading_token_and_keeps_rest() {
+        let aliases = env(&[("build", "cargo build --release")]);
+        assert_eq!(resolve_alias("build --quiet", &aliases), "cargo build --release --quiet");
+    }
+
+    #[test]
+    fn resolve_alias_with_no_match_returns_input_unchanged() {
+        let aliases = env(&[("build", "cargo build --release")]);
+        assert_eq!(resolve_alias("test --quiet", &aliases), "test --quiet");
+    }
+
+    #[test]
+    fn resolve_alias_with_no_trailing_args_expands_alone() {
+        let aliases = env(&[("build", "cargo build --release")]);
+        assert_eq!(resolve_alias("build", &aliases), "cargo build --release");
+    }
+
+    /// A scratch directory under the system temp dir, unique per test process
+    /// so parallel `cargo test` runs don't collide.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("cmdy_test_completer_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn completer_filters_entries_by_prefix() {
+        let dir = test_dir("prefix");
+        fs::write(dir.join("alpha.txt"), "").unwrap();
+        fs::write(dir.join("alt.txt"), "").unwrap();
+        fs::write(dir.join("beta.txt"), "").unwrap();
+
+        let line = dir.join("al").to_string_lossy().to_string();
+        let candidates = completer(&line);
+
+        assert_eq!(
+            candidates,
+            vec![
+                dir.join("alpha.txt").to_string_lossy().to_string(),
+                dir.join("alt.txt").to_string_lossy().to_string(),
+            ]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn completer_trailing_slash_lists_whole_directory() {
+        let dir = test_dir("trailing_slash");
+        fs::write(dir.join("one.txt"), "").unwrap();
+
+        let line = format!("{}/", dir.to_string_lossy());
+        let candidates = completer(&line);
+
+        assert_eq!(candidates, vec![dir.join("one.txt").to_string_lossy().to_string()]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn completer_marks_subdirectories_with_trailing_slash() {
+        let dir = test_dir("subdir");
+        fs::create_dir_all(dir.join("child")).unwrap();
+
+        let line = dir.join("ch").to_string_lossy().to_string();
+        let candidates = completer(&line);
+
+        assert_eq!(candidates, vec![format!("{}/", dir.join("child").to_string_lossy())]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn silent_sh(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(script).stdout(Stdio::null()).stderr(Stdio::null());
+        command
+    }
+
+    #[test]
+    fn run_watch_returns_exit_code_when_process_finishes_before_timeout() {
+        let code = run_watch(silent_sh("exit 3"), "exit 3", Some(5), true);
+        assert_eq!(code, 3);
+    }
+
+    #[test]
+    fn run_watch_kills_and_returns_minus_one_after_timeout() {
+        let code = run_watch(silent_sh("sleep 5"), "sleep 5", Some(1), true);
+        assert_eq!(code, -1);
+    }
+
+    #[test]
+    fn run_watch_returns_zero_when_left_running_without_kill_after() {
+        let code = run_watch(silent_sh("sleep 2"), "sleep 2", Some(1), false);
+        assert_eq!(code, 0);
     }
 }